@@ -5,6 +5,141 @@ use similar::{ChangeTag, TextDiff};
 
 const CONFIG_PATHS: &[&str] = &[".einstellung", "einstellung"];
 
+/// Number of unchanged lines kept around a changed run before it is collapsed.
+const DEFAULT_CONTEXT_SIZE: usize = 3;
+
+/// How line endings are chosen when a synced file is written back to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum NewlineStyle {
+    /// Match whatever the target file already uses, falling back to
+    /// [`NewlineStyle::Native`] for new or empty files.
+    #[default]
+    Auto,
+    Lf,
+    Crlf,
+    /// `\r\n` on Windows, `\n` everywhere else.
+    Native,
+}
+
+impl std::str::FromStr for NewlineStyle {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(NewlineStyle::Auto),
+            "lf" => Ok(NewlineStyle::Lf),
+            "crlf" => Ok(NewlineStyle::Crlf),
+            "native" => Ok(NewlineStyle::Native),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    fn native() -> Self {
+        if cfg!(windows) {
+            LineEnding::Crlf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// Scans `content` for the first line ending it contains.
+    fn detect(content: &str) -> Option<Self> {
+        content.find(['\n', '\r']).map(|index| {
+            if content[index..].starts_with("\r\n") {
+                LineEnding::Crlf
+            } else {
+                LineEnding::Lf
+            }
+        })
+    }
+}
+
+/// Normalizes all line endings in `content` to `\n` so that diffing and
+/// equality checks are insensitive to the original line-ending style.
+fn normalize_to_lf(content: &str) -> String {
+    content.replace("\r\n", "\n")
+}
+
+/// Re-applies `ending` to an already `\n`-normalized `content`.
+fn apply_line_ending(content: &str, ending: LineEnding) -> String {
+    match ending {
+        LineEnding::Lf => content.to_owned(),
+        LineEnding::Crlf => content.replace('\n', "\r\n"),
+    }
+}
+
+/// Resolves the line ending to write to `path` for the given `style`.
+fn resolve_line_ending(style: NewlineStyle, path: &str) -> LineEnding {
+    match style {
+        NewlineStyle::Lf => LineEnding::Lf,
+        NewlineStyle::Crlf => LineEnding::Crlf,
+        NewlineStyle::Native => LineEnding::native(),
+        NewlineStyle::Auto => fs::read_to_string(path)
+            .ok()
+            .and_then(|existing| LineEnding::detect(&existing))
+            .unwrap_or_else(LineEnding::native),
+    }
+}
+
+#[cfg(test)]
+mod newline_tests {
+    use super::*;
+
+    #[test]
+    fn normalize_to_lf_strips_crlf() {
+        assert_eq!(normalize_to_lf("a\r\nb\nc\r\n"), "a\nb\nc\n");
+    }
+
+    #[test]
+    fn apply_line_ending_lf_is_a_no_op() {
+        assert_eq!(apply_line_ending("a\nb\n", LineEnding::Lf), "a\nb\n");
+    }
+
+    #[test]
+    fn apply_line_ending_crlf_reintroduces_carriage_returns() {
+        assert_eq!(apply_line_ending("a\nb\n", LineEnding::Crlf), "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn resolve_line_ending_lf_and_crlf_ignore_the_target_file() {
+        assert_eq!(resolve_line_ending(NewlineStyle::Lf, "missing.txt"), LineEnding::Lf);
+        assert_eq!(resolve_line_ending(NewlineStyle::Crlf, "missing.txt"), LineEnding::Crlf);
+    }
+
+    #[test]
+    fn resolve_line_ending_native_matches_the_platform() {
+        assert_eq!(
+            resolve_line_ending(NewlineStyle::Native, "missing.txt"),
+            LineEnding::native()
+        );
+    }
+
+    #[test]
+    fn resolve_line_ending_auto_detects_the_existing_files_style() {
+        let path = std::env::temp_dir().join(format!("einstellung-test-{}.txt", std::process::id()));
+        fs::write(&path, "a\r\nb\r\n").unwrap();
+        let ending = resolve_line_ending(NewlineStyle::Auto, path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(ending, LineEnding::Crlf);
+    }
+
+    #[test]
+    fn resolve_line_ending_auto_falls_back_to_native_for_a_missing_file() {
+        assert_eq!(
+            resolve_line_ending(NewlineStyle::Auto, "definitely-missing-file.txt"),
+            LineEnding::native()
+        );
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 enum EinstellungError {
     #[error(transparent)]
@@ -21,18 +156,24 @@ enum EinstellungError {
 
     #[error("The configuration file {0} could not be saved ({1})")]
     FailedToSaveConfigurationFile(String, std::io::Error),
+
+    #[error("Some synced files are out of date")]
+    FilesOutOfSync,
 }
 
 fn main() -> Result<(), EinstellungError> {
-    match std::env::args()
-        .into_iter()
-        .nth(1)
-        .map(|arg| arg.to_lowercase())
-        .as_deref()
-        .map(|arg| arg.trim())
-    {
-        Some("read") => read(),
+    let args: Vec<String> = std::env::args().collect();
+    let context = args
+        .iter()
+        .skip(2)
+        .find_map(|arg| arg.strip_prefix("--context="))
+        .and_then(|value| value.parse().ok());
+
+    match args.get(1).map(|arg| arg.to_lowercase()).as_deref() {
+        Some("read") => sync(EmitMode::Interactive, context),
         Some("write") => write(),
+        Some("check") => sync(EmitMode::Check, context),
+        Some("diff") => sync(EmitMode::Diff, context),
         _ => help(),
     }
 }
@@ -43,12 +184,20 @@ fn help() -> Result<(), EinstellungError> {
     return Ok(());
 }
 
-fn read_configuration() -> Result<Vec<(String, Vec<String>)>, EinstellungError> {
+struct Configuration {
+    context: usize,
+    newline_style: NewlineStyle,
+    syncs: Vec<(String, Vec<String>)>,
+}
+
+fn read_configuration() -> Result<Configuration, EinstellungError> {
     let configuration = CONFIG_PATHS
         .iter()
         .find_map(|name| fs::read_to_string(name).ok())
         .ok_or(EinstellungError::ConfigurationMissing)?;
 
+    let mut context = DEFAULT_CONTEXT_SIZE;
+    let mut newline_style = NewlineStyle::default();
     let syncs = configuration
         .lines()
         .flat_map(|line| {
@@ -59,6 +208,18 @@ fn read_configuration() -> Result<Vec<(String, Vec<String>)>, EinstellungError>
             if original_file.starts_with('#') {
                 return None;
             }
+            if original_file == "context" {
+                if let Some(value) = parts.next().and_then(|part| part.parse().ok()) {
+                    context = value;
+                }
+                return None;
+            }
+            if original_file == "newline" {
+                if let Some(value) = parts.next().and_then(|part| part.parse().ok()) {
+                    newline_style = value;
+                }
+                return None;
+            }
 
             Some((
                 original_file.to_owned(),
@@ -67,117 +228,463 @@ fn read_configuration() -> Result<Vec<(String, Vec<String>)>, EinstellungError>
         })
         .collect();
 
-    Ok(syncs)
+    Ok(Configuration {
+        context,
+        newline_style,
+        syncs,
+    })
+}
+
+/// How a synced file pair is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EmitMode {
+    /// The original interactive accept/reject loop, writing accepted
+    /// changes back to the original file.
+    Interactive,
+    /// Only report which files are out of date; nothing is written.
+    Check,
+    /// Print a unified diff of what `Interactive` would apply.
+    Diff,
+    /// Write the original file's content back to each other file.
+    WriteBack,
 }
 
-fn read() -> Result<(), EinstellungError> {
+/// Expands `other_file` and reads its content, reporting any failure
+/// through `term` the same way for every [`EmitMode`]. Returns `None` once
+/// the failure has been reported so the caller can move on to the next
+/// file.
+fn load_other_file(
+    term: &mut Term,
+    other_file: &str,
+) -> Result<Option<(String, String)>, EinstellungError> {
+    let Ok(expanded) = shellexpand::full(other_file) else {
+        writeln!(term, "  Invalid file name {}", other_file)?;
+        return Ok(None);
+    };
+
+    let Ok(content) = fs::read_to_string(expanded.deref()) else {
+        writeln!(term, "  Not found {}", expanded)?;
+        return Ok(None);
+    };
+
+    Ok(Some((expanded.into_owned(), content)))
+}
+
+fn sync(mode: EmitMode, context: Option<usize>) -> Result<(), EinstellungError> {
     let mut term = Term::stdout();
-    term.show_cursor()?;
+    if matches!(mode, EmitMode::Interactive | EmitMode::WriteBack) {
+        term.show_cursor()?;
+    }
 
     let configuration = read_configuration()?;
-
-    for (original_file, other_files) in configuration {
-        writeln!(term, "Read for {original_file}")?;
+    let context = context.unwrap_or(configuration.context);
+    let mut out_of_date = false;
+
+    for (original_file, other_files) in configuration.syncs {
+        match mode {
+            EmitMode::Interactive => writeln!(term, "Read for {original_file}")?,
+            EmitMode::WriteBack => writeln!(term, "Write for {original_file}")?,
+            EmitMode::Check | EmitMode::Diff => {}
+        }
 
         let original_content = fs::read_to_string(&original_file)
             .map_err(|err| EinstellungError::SyncFileMissing(original_file.to_owned(), err))?;
         for other_file in other_files {
-            let Ok(other_file) = shellexpand::full(&other_file) else {
-                writeln!(term, "  Invalid file name {}", other_file)?;
+            let Some((other_file, other_content)) = load_other_file(&mut term, &other_file)?
+            else {
                 continue;
             };
 
-            let Ok(other_content) = fs::read_to_string(other_file.deref()) else {
-                writeln!(term, "  Not found {}", other_file)?;
-                continue;
-            };
-            writeln!(term, "  Compare with {}", other_file)?;
-            let content = compare_files(&mut term, &original_content, &other_content)?;
-            if let Some(content) = content {
-                fs::write(&original_file, content).map_err(|err| {
-                    EinstellungError::FailedToSaveSyncFile(original_file.to_owned(), err)
-                })?;
+            let original_content = normalize_to_lf(&original_content);
+            let other_content = normalize_to_lf(&other_content);
+
+            match mode {
+                EmitMode::Interactive => {
+                    writeln!(term, "  Compare with {}", other_file)?;
+                    let content =
+                        compare_files(&mut term, &original_content, &other_content, context)?;
+                    if let Some(content) = content {
+                        let ending =
+                            resolve_line_ending(configuration.newline_style, &original_file);
+                        fs::write(&original_file, apply_line_ending(&content, ending)).map_err(
+                            |err| EinstellungError::FailedToSaveSyncFile(original_file.to_owned(), err),
+                        )?;
+                    }
+                }
+                EmitMode::Check => {
+                    if original_content != other_content {
+                        out_of_date = true;
+                        println!("{original_file} is out of date with {other_file}");
+                    }
+                }
+                EmitMode::Diff => {
+                    if original_content != other_content {
+                        out_of_date = true;
+                        print_unified_diff(
+                            &original_file,
+                            &other_file,
+                            &original_content,
+                            &other_content,
+                            context,
+                        );
+                    }
+                }
+                EmitMode::WriteBack => {
+                    writeln!(term, "  Compare to {}", other_file)?;
+                    if original_content == other_content {
+                        continue;
+                    }
+
+                    if read_save_question(&mut term)? {
+                        let ending =
+                            resolve_line_ending(configuration.newline_style, &other_file);
+                        let content = apply_line_ending(&original_content, ending);
+                        fs::write(&other_file, content).map_err(|err| {
+                            EinstellungError::FailedToSaveConfigurationFile(
+                                original_file.to_owned(),
+                                err,
+                            )
+                        })?;
+                    }
+                }
             }
         }
     }
 
-    write!(term, "\r")?;
+    if matches!(mode, EmitMode::Interactive | EmitMode::WriteBack) {
+        write!(term, "\r")?;
+    }
+
+    if out_of_date {
+        return Err(EinstellungError::FilesOutOfSync);
+    }
 
     Ok(())
 }
 
+/// The 1-based line number unified diff headers use for `range`: the line
+/// itself, unless `range` is empty, in which case it's the position between
+/// lines (GNU diff convention, e.g. `@@ -1,0 +2 @@` for a pure insertion).
+fn diff_header_start(range: &std::ops::Range<usize>) -> usize {
+    if range.is_empty() {
+        range.start
+    } else {
+        range.start + 1
+    }
+}
+
+/// Formats one side of a hunk header, e.g. `1,3` or, for a single line, just
+/// `1` (the `,1` count is conventionally omitted, matching GNU diff).
+fn format_diff_range(range: &std::ops::Range<usize>) -> String {
+    let start = diff_header_start(range);
+    if range.len() == 1 {
+        format!("{start}")
+    } else {
+        format!("{start},{}", range.len())
+    }
+}
+
+fn format_hunk_header(old_range: &std::ops::Range<usize>, new_range: &std::ops::Range<usize>) -> String {
+    format!(
+        "@@ -{} +{} @@",
+        format_diff_range(old_range),
+        format_diff_range(new_range)
+    )
+}
+
+fn print_unified_diff(
+    original_label: &str,
+    other_label: &str,
+    original_content: &str,
+    other_content: &str,
+    context: usize,
+) {
+    println!("--- {original_label}");
+    println!("+++ {other_label}");
+
+    let diff = TextDiff::from_lines(original_content, other_content);
+    for group in diff.grouped_ops(context) {
+        let old_range = group.first().expect("group is never empty").old_range().start
+            ..group.last().expect("group is never empty").old_range().end;
+        let new_range = group.first().expect("group is never empty").new_range().start
+            ..group.last().expect("group is never empty").new_range().end;
+        println!("{}", format_hunk_header(&old_range, &new_range));
+
+        for change in group.iter().flat_map(|op| diff.iter_changes(op)) {
+            let prefix = tag_prefix(change.tag());
+            print!("{prefix}{}", change.value());
+        }
+    }
+}
+
+#[cfg(test)]
+mod unified_diff_header_tests {
+    use super::*;
+
+    #[test]
+    fn format_hunk_header_numbers_nonempty_ranges_from_one() {
+        assert_eq!(format_hunk_header(&(0..3), &(0..3)), "@@ -1,3 +1,3 @@");
+    }
+
+    #[test]
+    fn format_hunk_header_numbers_a_pure_insertion_by_position() {
+        // inserting a line after original line 1 touches no original lines
+        assert_eq!(format_hunk_header(&(1..1), &(1..2)), "@@ -1,0 +2 @@");
+    }
+
+    #[test]
+    fn format_hunk_header_numbers_a_pure_deletion_by_position() {
+        assert_eq!(format_hunk_header(&(1..2), &(1..1)), "@@ -2 +1,0 @@");
+    }
+}
+
+/// One group from `TextDiff::grouped_ops`: one or more runs of changed lines
+/// that lie close together, plus the unchanged context around them.
+struct Hunk {
+    /// 0-based, half-open line range this hunk occupies in the original file.
+    original_range: std::ops::Range<usize>,
+    /// 0-based, half-open line range this hunk occupies in the incoming file.
+    new_range: std::ops::Range<usize>,
+    lines: Vec<(ChangeTag, String)>,
+}
+
+fn build_hunks(diff: &TextDiff<str>, groups: &[Vec<similar::DiffOp>]) -> Vec<Hunk> {
+    groups
+        .iter()
+        .map(|group| Hunk {
+            original_range: group.first().expect("group is never empty").old_range().start
+                ..group.last().expect("group is never empty").old_range().end,
+            new_range: group.first().expect("group is never empty").new_range().start
+                ..group.last().expect("group is never empty").new_range().end,
+            lines: group
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| (change.tag(), change.value().to_owned()))
+                .collect(),
+        })
+        .collect()
+}
+
+fn format_line_range(range: &std::ops::Range<usize>) -> String {
+    if range.is_empty() {
+        format!("before line {}", range.start + 1)
+    } else if range.len() == 1 {
+        format!("line {}", range.start + 1)
+    } else {
+        format!("lines {}-{}", range.start + 1, range.end)
+    }
+}
+
+#[cfg(test)]
+mod hunk_tests {
+    use super::*;
+
+    #[test]
+    fn format_line_range_numbers_a_single_line() {
+        assert_eq!(format_line_range(&(2..3)), "line 3");
+    }
+
+    #[test]
+    fn format_line_range_numbers_a_multi_line_span() {
+        assert_eq!(format_line_range(&(2..5)), "lines 3-5");
+    }
+
+    #[test]
+    fn format_line_range_describes_a_pure_insertion_by_position() {
+        assert_eq!(format_line_range(&(3..3)), "before line 4");
+    }
+
+    #[test]
+    fn build_hunks_captures_both_sides_of_a_pure_insertion() {
+        let diff = TextDiff::from_lines("a\nb\n", "a\nNEW\nb\n");
+        let groups = diff.grouped_ops(0);
+        let hunks = build_hunks(&diff, &groups);
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].original_range, 1..1);
+        assert_eq!(hunks[0].new_range, 1..2);
+    }
+
+    #[test]
+    fn build_hunks_captures_changed_lines_on_both_sides() {
+        let diff = TextDiff::from_lines("a\nb\nc\n", "a\nB\nc\n");
+        let groups = diff.grouped_ops(0);
+        let hunks = build_hunks(&diff, &groups);
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].original_range, 1..2);
+        assert_eq!(hunks[0].new_range, 1..2);
+    }
+}
+
+/// Renders `hunk` with its "hunk i of N" / line-number header and returns
+/// the number of terminal lines written, so the caller can clear them again
+/// before re-rendering.
+fn render_hunk(
+    term: &mut Term,
+    hunk: &Hunk,
+    index: usize,
+    total: usize,
+    decision: Option<bool>,
+) -> Result<usize, EinstellungError> {
+    let range = if hunk.original_range == hunk.new_range {
+        format_line_range(&hunk.original_range)
+    } else {
+        format!(
+            "{} → {}",
+            format_line_range(&hunk.original_range),
+            format_line_range(&hunk.new_range)
+        )
+    };
+    let header = style(format!("hunk {} of {} ({range})", index + 1, total)).bold();
+    writeln!(term, "{header}")?;
+
+    for (tag, text) in &hunk.lines {
+        let style = tag_style(*tag);
+        let style = match (tag, decision) {
+            (ChangeTag::Equal, _) => style,
+            (_, Some(false)) => style.dim(),
+            _ => style.bold(),
+        };
+        let prefix = tag_prefix(*tag);
+        writeln!(term, " {prefix} {}", style.apply_to(text.trim_ascii_end()))?;
+    }
+
+    Ok(hunk.lines.len() + 1)
+}
+
+enum HunkInput {
+    Accept,
+    Reject,
+    AcceptRest,
+    RejectRest,
+    Previous,
+    Next,
+}
+
+fn read_hunk_input(term: &mut Term) -> Result<HunkInput, EinstellungError> {
+    loop {
+        let input = match term.read_key()? {
+            console::Key::Char(c) => match c.to_ascii_lowercase() {
+                'a' => HunkInput::Accept,
+                's' => HunkInput::Reject,
+                'd' => HunkInput::AcceptRest,
+                'f' => HunkInput::RejectRest,
+                _ => continue,
+            },
+            console::Key::ArrowLeft => HunkInput::Previous,
+            console::Key::ArrowRight => HunkInput::Next,
+            _ => continue,
+        };
+        return Ok(input);
+    }
+}
+
 fn compare_files(
     term: &mut Term,
     original_content: &str,
     other_content: &str,
+    context: usize,
 ) -> Result<Option<String>, EinstellungError> {
-    let diff = TextDiff::from_lines(original_content, &other_content);
-    if diff
-        .iter_all_changes()
-        .all(|change| change.tag() == ChangeTag::Equal)
-    {
+    let diff = TextDiff::from_lines(original_content, other_content);
+    let groups = diff.grouped_ops(context);
+    if groups.is_empty() {
         return Ok(None);
     }
 
-    let hint = style("> A: keep | S: remove | D: keep block | F: remove block").bold();
+    let hunks = build_hunks(&diff, &groups);
+    let total = hunks.len();
+
+    // number of unchanged lines collapsed between the previous hunk (or the
+    // start of the file) and each hunk
+    let mut last_old_end = 0;
+    let gaps: Vec<usize> = hunks
+        .iter()
+        .map(|hunk| {
+            let gap = hunk.original_range.start - last_old_end;
+            last_old_end = hunk.original_range.end;
+            gap
+        })
+        .collect();
+    let trailing_gap = original_content.lines().count().saturating_sub(last_old_end);
+
+    let hint = style("> A: accept | S: reject | D: accept rest | F: reject rest | ←/→: move").bold();
     writeln!(term, "{hint}")?;
 
-    // print changes and move the cursor to the top
-    let mut resulting_content = String::new();
-    let mut changed = false;
-    let mut lines = 0;
-    for change in diff.iter_all_changes() {
-        lines += 1;
-        let style = tag_style(change.tag());
-        let prefix = tag_prefix(change.tag());
-        let text = style.apply_to(change.value().trim_ascii_end());
-        writeln!(term, " {prefix} {text}")?;
-    }
-    term.move_cursor_up(lines)?;
-
-    // go through the lines and build the resulting content and
-    // dim removed lines
-    let mut automatic = None;
-    for change in diff.iter_all_changes() {
-        if let ChangeTag::Equal = change.tag() {
-            resulting_content.push_str(change.value());
-            term.move_cursor_down(1)?;
-            continue;
+    let mut decisions: Vec<Option<bool>> = vec![None; total];
+    let mut position = 0;
+    let mut printed_lines = 0;
+    while position < total {
+        term.clear_last_lines(printed_lines)?;
+        printed_lines = 0;
+        if gaps[position] > 0 {
+            let marker = style(format!(" ⋯ ({} unchanged lines)", gaps[position])).dim();
+            writeln!(term, "{marker}")?;
+            printed_lines += 1;
         }
+        printed_lines += render_hunk(term, &hunks[position], position, total, decisions[position])?;
 
-        let style = tag_style(change.tag());
-        let prefix = tag_prefix(change.tag());
-        term.clear_line()?;
-        let text = style
-            .clone()
-            .bold()
-            .apply_to(change.value().trim_ascii_end());
-        write!(term, " {prefix} {text}\r",)?;
-
-        let accept = if let Some((tag, accept)) = automatic
-            && tag == change.tag()
-        {
-            accept
-        } else {
-            let (accept, auto_accept) = read_accept_input(term)?;
-            automatic = auto_accept.map(|accept| (change.tag(), accept));
-            accept
-        };
+        match read_hunk_input(term)? {
+            HunkInput::Accept => {
+                decisions[position] = Some(true);
+                position += 1;
+            }
+            HunkInput::Reject => {
+                decisions[position] = Some(false);
+                position += 1;
+            }
+            HunkInput::AcceptRest => {
+                for decision in &mut decisions[position..] {
+                    *decision = Some(true);
+                }
+                position = total;
+            }
+            HunkInput::RejectRest => {
+                for decision in &mut decisions[position..] {
+                    *decision = Some(false);
+                }
+                position = total;
+            }
+            HunkInput::Previous => position = position.saturating_sub(1),
+            HunkInput::Next => position = (position + 1).min(total.saturating_sub(1)),
+        }
+    }
+    term.clear_last_lines(printed_lines)?;
+    if trailing_gap > 0 {
+        let marker = style(format!(" ⋯ ({trailing_gap} unchanged lines)")).dim();
+        writeln!(term, "{marker}")?;
+    }
 
-        let style = if accept {
-            resulting_content.push_str(change.value());
-            style
-        } else {
-            style.dim()
-        };
-        let text = style.apply_to(change.value().trim_ascii_end());
-        term.clear_line()?;
-        write!(term, " {prefix} {text}",)?;
+    // assemble the resulting content, driven entirely off the accepted hunks
+    let original_lines: Vec<&str> = original_content.split_inclusive('\n').collect();
+    let mut resulting_content = String::new();
+    let mut changed = false;
+    let mut last_old_end = 0;
 
-        changed |= accept == matches!(change.tag(), ChangeTag::Insert);
+    for (hunk_index, hunk) in hunks.iter().enumerate() {
+        if hunk.original_range.start > last_old_end {
+            resulting_content
+                .push_str(&original_lines[last_old_end..hunk.original_range.start].concat());
+        }
 
-        term.move_cursor_down(1)?;
+        // undecided hunks default to rejected, i.e. keep the original content
+        let accept = decisions[hunk_index].unwrap_or(false);
+        changed |= accept;
+        for (tag, text) in &hunk.lines {
+            match tag {
+                ChangeTag::Equal => resulting_content.push_str(text),
+                ChangeTag::Insert if accept => resulting_content.push_str(text),
+                ChangeTag::Delete if !accept => resulting_content.push_str(text),
+                _ => {}
+            }
+        }
+
+        last_old_end = hunk.original_range.end;
+    }
+    if last_old_end < original_lines.len() {
+        resulting_content.push_str(&original_lines[last_old_end..].concat());
     }
+
     Ok((changed && read_save_question(term)?).then_some(resulting_content))
 }
 
@@ -197,19 +704,6 @@ fn tag_prefix(change: ChangeTag) -> char {
     }
 }
 
-fn read_accept_input(term: &mut Term) -> Result<(bool, Option<bool>), EinstellungError> {
-    loop {
-        let res = match term.read_char()?.to_ascii_lowercase() {
-            'a' => (true, None),
-            's' => (false, None),
-            'd' => (true, Some(true)),
-            'f' => (false, Some(false)),
-            _ => continue,
-        };
-        return Ok(res);
-    }
-}
-
 fn read_save_question(term: &mut Term) -> Result<bool, EinstellungError> {
     let hint = style("> S: save | D: discard").bold();
     writeln!(term, "{hint}")?;
@@ -224,40 +718,5 @@ fn read_save_question(term: &mut Term) -> Result<bool, EinstellungError> {
 }
 
 fn write() -> Result<(), EinstellungError> {
-    let mut term = Term::stdout();
-    term.show_cursor()?;
-
-    let configuration = read_configuration()?;
-
-    for (original_file, other_files) in configuration {
-        writeln!(term, "Write for {original_file}")?;
-
-        let original_content = fs::read_to_string(&original_file)
-            .map_err(|err| EinstellungError::SyncFileMissing(original_file.to_owned(), err))?;
-        for other_file in other_files {
-            let Ok(other_file) = shellexpand::full(&other_file) else {
-                writeln!(term, "  Invalid file name {}", other_file)?;
-                continue;
-            };
-
-            let Ok(other_content) = fs::read_to_string(other_file.deref()) else {
-                writeln!(term, "  Not found {}", other_file)?;
-                continue;
-            };
-            writeln!(term, "  Compare to {}", other_file)?;
-            if original_content == other_content {
-                continue;
-            }
-
-            if read_save_question(&mut term)? {
-                fs::write(other_file.deref(), &original_content).map_err(|err| {
-                    EinstellungError::FailedToSaveConfigurationFile(original_file.to_owned(), err)
-                })?;
-            }
-        }
-    }
-
-    write!(term, "\r")?;
-
-    Ok(())
+    sync(EmitMode::WriteBack, None)
 }